@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use clap::builder::Styles;
 use clap::builder::styling::{AnsiColor, Effects};
 
@@ -35,6 +37,17 @@ pub(crate) struct SyuArgs {
     #[arg(short, long, group = "output", visible_alias = "dry-run")]
     pub(crate) list: bool,
 
+    /// Reconcile installed packages against a declarative manifest file.
+    ///
+    /// Defaults to `~/.config/cargo-syu/packages.toml` if it exists. Packages not listed in the
+    /// manifest are left untouched.
+    #[arg(short, long, name = "PATH")]
+    pub(crate) manifest: Option<PathBuf>,
+
+    /// Output format for the package report.
+    #[arg(long, value_enum, default_value_t = Format::Table)]
+    pub(crate) format: Format,
+
     #[command(flatten)]
     pub(crate) package_args: PackageArgs,
 
@@ -42,12 +55,35 @@ pub(crate) struct SyuArgs {
     pub(crate) install_args: InstallArgs,
 }
 
+/// Output format for the package report, analogous to `cargo info`'s human/JSON modes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+#[clap(rename_all = "lowercase")]
+pub(crate) enum Format {
+    /// Colored, human-readable table (the default).
+    Table,
+    /// One JSON object holding a `packages` array of package reports and, in manifest mode, a
+    /// `manifest_actions` array of planned installs/uninstalls, suitable for piping into `jq` or CI.
+    Json,
+}
+
 #[derive(clap::Args)]
 #[command(next_help_heading = "Package Selection")]
 pub(crate) struct PackageArgs {
     /// Comma separated list of packages to exclude.
     #[arg(short, long, name = "PACKAGE", value_delimiter = ',')]
     pub(crate) exclude: Option<Vec<String>>,
+
+    /// Consider pre-release registry versions as available updates.
+    #[arg(long)]
+    pub(crate) pre: bool,
+
+    /// Only consider packages with a semver-compatible update available.
+    #[arg(long, conflicts_with = "incompatible")]
+    pub(crate) compatible: bool,
+
+    /// Only consider packages with a breaking (semver-incompatible) update available.
+    #[arg(long, conflicts_with = "compatible")]
+    pub(crate) incompatible: bool,
 }
 
 #[derive(clap::Args)]
@@ -66,6 +102,13 @@ pub(crate) struct InstallArgs {
     /// Use verbose output.
     #[arg(short, long)]
     pub(crate) verbose: bool,
+
+    /// Install registry packages via `cargo binstall` instead of building from source.
+    ///
+    /// Falls back to `cargo install` when `cargo-binstall` isn't on PATH or has no prebuilt
+    /// artifact for this target.
+    #[arg(long)]
+    pub(crate) binstall: bool,
 }
 
 #[cfg(test)]
@@ -36,16 +36,18 @@
 //! - [x] Find and update git packages.
 //! - [ ] Detect registry URL from `.crates.toml`.
 //! - [ ] Print progress bar for metadata fetching.
-//! - [ ] Improved handling of errors
-//!     1. [ ] Don't fail immediately if one package operation failed.
-//!     2. [ ] Display warning for failed package, but continue for remaining packages.
+//! - [x] Improved handling of errors
+//!     1. [x] Don't fail immediately if one package operation failed.
+//!     2. [x] Display warning for failed package, but continue for remaining packages.
 //! - [ ] Allow more or less verbose output.
 //! - [ ] Allow printing of outdated packages only.
 //! - [ ] Add more code documentation.
 //! - [ ] Write unit and integration tests.
 //! - [x] Add --ask flag to require user confirmation before installing packages.
+//! - [x] Reconcile installed packages against a declarative `packages.toml` manifest.
 
 mod cli;
+mod manifest;
 
 use core::str::FromStr;
 use std::path::PathBuf;
@@ -61,6 +63,7 @@ use rayon::iter::{IntoParallelIterator as _, ParallelIterator as _};
 use semver::Version;
 
 use crate::cli::{Cli, InstallArgs, PackageArgs};
+use crate::manifest::Manifest;
 
 fn main() -> Result<()> {
     let Cli::Syu(args) = Cli::parse();
@@ -90,14 +93,35 @@ fn main() -> Result<()> {
         .and_then(|v1| v1.as_table())
         .context("Couldn't read crates from `.crates.toml`")?;
 
-    let crates = crates
+    let all_crates = crates
         .into_iter()
         .map(|(pkg, _)| Package::from_str(pkg))
         .collect::<Result<Vec<_>>>()?;
 
+    let manifest_path = args.manifest.clone().or_else(Manifest::default_path);
+    let manifest = manifest_path
+        .filter(|path| path.exists())
+        .map(|path| Manifest::read(&path))
+        .transpose()?;
+
+    // Reconcile against the full, unfiltered set of installed packages: `--exclude` and `--git`
+    // only govern what the update pipeline below touches, they must not hide a package from the
+    // manifest, or `absent` would silently fail to uninstall a git-installed tool, and
+    // `present`/`latest` would treat an already-installed git package as missing and reinstall
+    // it from the registry instead.
+    let manifest_actions = manifest
+        .as_ref()
+        .map(|manifest| manifest::plan(manifest, &all_crates))
+        .unwrap_or_default();
+
     // Filter package list based on upstream and command line arguments.
-    let PackageArgs { exclude } = args.package_args;
-    let crates = crates
+    let PackageArgs {
+        exclude,
+        pre,
+        compatible,
+        incompatible,
+    } = args.package_args;
+    let crates = all_crates
         .into_iter()
         // Filter invalid packages and packages with a local source.
         .filter(|pkg| !matches!(pkg.upstream, Upstream::Unknown))
@@ -111,31 +135,85 @@ fn main() -> Result<()> {
         .filter(|pkg| args.git || matches!(pkg.upstream, Upstream::Registry { .. }))
         .collect::<Vec<_>>();
 
+    // In manifest mode, only crates the manifest marks `latest` flow through the regular
+    // fetch-and-update pipeline below; `present` crates are left alone once installed, and
+    // crates the manifest doesn't mention aren't touched at all.
+    let crates = if let Some(manifest) = &manifest {
+        crates
+            .into_iter()
+            .filter(|pkg| {
+                manifest
+                    .packages
+                    .get(&pkg.name)
+                    .is_some_and(|entry| entry.state() == manifest::DesiredState::Latest)
+            })
+            .collect::<Vec<_>>()
+    } else {
+        crates
+    };
+
+    // Fetch metadata for every package without letting one failure (a dead registry mirror, an
+    // unreachable git remote, ...) abort the whole run.
     let crates: Vec<LatestPackage> = crates
         .into_par_iter()
-        .map(Package::fetch_latest_version)
-        .collect::<Result<_>>()?;
-
-    let len = crates
-        .iter()
-        .map(|pkg| pkg.name.len())
-        .max()
-        .unwrap_or(7)
-        .max(7);
-    println!(
-        "{:>12} {:<len$} {:>9} {:>9}",
-        "Status".bold().green(),
-        "Package",
-        "Installed",
-        "Available"
-    );
-    crates.iter().for_each(|pkg| pkg.print(len));
+        .map(|pkg| {
+            let name = pkg.name.clone();
+            pkg.fetch_latest_version(pre).map_err(|err| (name, err))
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .filter_map(|result| match result {
+            Ok(pkg) => Some(pkg),
+            Err((name, err)) => {
+                eprintln!("{} failed to fetch {name}: {err}", "Warning".bold().yellow());
+                None
+            }
+        })
+        .collect();
+
+    let crates = crates
+        .into_iter()
+        .filter(|pkg| pkg.matches_compat_filter(compatible, incompatible))
+        .collect::<Vec<_>>();
+
+    match args.format {
+        cli::Format::Json => {
+            let packages = crates.iter().map(LatestPackage::report).collect::<Vec<_>>();
+            let manifest_actions = manifest_actions
+                .iter()
+                .map(manifest::ManifestAction::report)
+                .collect::<Vec<_>>();
+            let report = JsonReport {
+                packages,
+                manifest_actions,
+            };
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        cli::Format::Table => {
+            let len = crates
+                .iter()
+                .map(|pkg| pkg.name.len())
+                .max()
+                .unwrap_or(7)
+                .max(7);
+            println!(
+                "{:>12} {:<len$} {:>9} {:>9}",
+                "Status".bold().green(),
+                "Package",
+                "Installed",
+                "Available"
+            );
+            crates.iter().for_each(|pkg| pkg.print(len));
+            manifest::print_plan(&manifest_actions);
+        }
+    }
 
     if args.list {
         return Ok(());
     }
 
-    if !crates.is_empty() && args.ask && prompt_confirmation("Install packages?").unwrap_or(false) {
+    let pending = !crates.is_empty() || !manifest_actions.is_empty();
+    if pending && args.ask && !prompt_confirmation("Install packages?").unwrap_or(false) {
         return Ok(());
     }
 
@@ -143,10 +221,53 @@ fn main() -> Result<()> {
         jobs,
         no_locked,
         verbose,
+        binstall,
     } = args.install_args;
-    crates
-        .into_iter()
-        .try_for_each(|pkg| pkg.update(jobs, !no_locked, verbose))?;
+    let locked = !no_locked;
+
+    // Attempt every outdated package instead of bailing out on the first failure, so a single
+    // broken build doesn't block the rest of a large tool set.
+    let mut updated = 0u32;
+    let mut failed = 0u32;
+    let mut skipped = 0u32;
+
+    for pkg in &crates {
+        if !pkg.has_update() {
+            skipped += 1;
+            continue;
+        }
+        match update(pkg, jobs, locked, verbose, binstall) {
+            Ok(()) => updated += 1,
+            Err(err) => {
+                failed += 1;
+                eprintln!(
+                    "{} failed to update {}: {err}",
+                    "Warning".bold().yellow(),
+                    pkg.name
+                );
+            }
+        }
+    }
+
+    for action in &manifest_actions {
+        match action.run() {
+            Ok(()) => updated += 1,
+            Err(err) => {
+                failed += 1;
+                eprintln!(
+                    "{} failed to apply manifest action for {}: {err}",
+                    "Warning".bold().yellow(),
+                    action.name()
+                );
+            }
+        }
+    }
+
+    println!("{updated} updated, {failed} failed, {skipped} skipped");
+
+    if failed > 0 {
+        bail!("{failed} package operation(s) failed");
+    }
 
     Ok(())
 }
@@ -161,17 +282,48 @@ struct CargoInstallConfig {
     root: Option<PathBuf>,
 }
 
-struct Package {
-    name: String,
-    upstream: Upstream,
+pub(crate) struct Package {
+    pub(crate) name: String,
+    pub(crate) upstream: Upstream,
 }
 
-enum Upstream {
-    Git { url: String, commit: String },
-    Registry { version: Version },
+pub(crate) enum Upstream {
+    Git {
+        url: String,
+        commit: String,
+        git_ref: GitRef,
+    },
+    Registry {
+        version: Version,
+    },
     Unknown,
 }
 
+/// The ref a git package was installed from, as encoded in the `.crates.toml` source URL's
+/// `?branch=`/`?tag=`/`?rev=` query. `cargo install --git` records exactly one of these (or
+/// none, for the default branch), and reinstalling must pass the same one back so the package
+/// doesn't silently drift onto the remote's default branch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum GitRef {
+    Branch(String),
+    Tag(String),
+    Rev(String),
+    Default,
+}
+
+impl GitRef {
+    /// Parse the `branch=`/`tag=`/`rev=` query string from a `.crates.toml` git source URL.
+    /// Anything else (including an empty query) is treated as the default branch.
+    fn from_query(query: &str) -> Self {
+        match query.split_once('=') {
+            Some(("branch", value)) => Self::Branch(value.to_owned()),
+            Some(("tag", value)) => Self::Tag(value.to_owned()),
+            Some(("rev", value)) => Self::Rev(value.to_owned()),
+            _ => Self::Default,
+        }
+    }
+}
+
 impl FromStr for Package {
     type Err = Error;
 
@@ -199,11 +351,18 @@ impl FromStr for Package {
                 let (url, commit) = url
                     .split_once('#')
                     .context(format!("Failed to split git commit: {url}"))?;
-                let url = url.split_once('?').map_or(url, |s| s.0);
+                let (url, git_ref) = match url.split_once('?') {
+                    Some((url, query)) => (url, GitRef::from_query(query)),
+                    None => (url, GitRef::Default),
+                };
 
                 let url = url.to_owned();
                 let commit = commit.to_owned();
-                Upstream::Git { url, commit }
+                Upstream::Git {
+                    url,
+                    commit,
+                    git_ref,
+                }
             }
             "registry" | "sparse" => {
                 let vers = Version::from_str(vers)?;
@@ -216,27 +375,28 @@ impl FromStr for Package {
 }
 
 impl Package {
-    fn fetch_latest_version(self) -> Result<LatestPackage> {
+    /// Fetch the latest available version/commit for this package. `allow_pre` controls whether
+    /// pre-release registry versions are considered; yanked versions are always skipped.
+    fn fetch_latest_version(self, allow_pre: bool) -> Result<LatestPackage> {
         let name = self.name;
         let upstream = match self.upstream {
-            Upstream::Git { url, commit } => {
+            Upstream::Git {
+                url,
+                commit,
+                git_ref,
+            } => {
                 let dir = tempfile::tempdir()?;
 
                 let repo = Repository::init_bare(dir)?;
                 let mut remote = repo.remote_anonymous(&url)?;
                 let conn = remote.connect_auth(Direction::Fetch, None, None)?;
-
-                let latest_commit = conn
-                    .list()?
-                    .iter()
-                    .next()
-                    .context("Failed to get HEAD commit hash")
-                    .map(|head| head.oid().to_string())?;
+                let latest_commit = resolve_remote_commit(&conn, &git_ref)?;
 
                 LatestUpstream::Git {
                     url,
                     commit,
                     latest_commit,
+                    git_ref,
                 }
             }
             Upstream::Registry { version } => {
@@ -246,12 +406,18 @@ impl Package {
                 );
                 let body = ureq::get(&url).call()?.into_string()?;
 
-                let latest_entry = body
+                // The sparse index lists one JSON object per published version, in publish
+                // order, including yanked releases and pre-releases: the last line is not
+                // necessarily the newest usable release, so parse every line and pick the
+                // actual maximum by semver ordering.
+                let latest_version = body
                     .lines()
-                    .last()
-                    .context(format!("Package index empty for {name}"))?;
-                let latest_registry_version: RegistryVersion = serde_json::from_str(latest_entry)?;
-                let latest_version = latest_registry_version.vers;
+                    .filter_map(|line| serde_json::from_str::<RegistryVersion>(line).ok())
+                    .filter(|entry| !entry.yanked)
+                    .filter(|entry| allow_pre || entry.vers.pre.is_empty())
+                    .map(|entry| entry.vers)
+                    .max()
+                    .context(format!("No available version found for {name}"))?;
 
                 LatestUpstream::Registry {
                     version,
@@ -266,6 +432,41 @@ impl Package {
     }
 }
 
+/// Resolve the commit a git package should be compared against, honoring the `branch`/`tag`/
+/// `rev` it was originally installed with instead of always following the remote's default
+/// branch.
+fn resolve_remote_commit(conn: &git2::Connection<'_>, git_ref: &GitRef) -> Result<String> {
+    match git_ref {
+        GitRef::Default => conn
+            .list()?
+            .iter()
+            .next()
+            .context("Failed to get HEAD commit hash")
+            .map(|head| head.oid().to_string()),
+        GitRef::Branch(branch) => {
+            let name = format!("refs/heads/{branch}");
+            conn.list()?
+                .iter()
+                .find(|head| head.name() == name)
+                .context(format!("Remote branch not found: {branch}"))
+                .map(|head| head.oid().to_string())
+        }
+        GitRef::Tag(tag) => {
+            let peeled = format!("refs/tags/{tag}^{{}}");
+            let plain = format!("refs/tags/{tag}");
+            let refs = conn.list()?;
+            refs.iter()
+                .find(|head| head.name() == peeled)
+                .or_else(|| refs.iter().find(|head| head.name() == plain))
+                .context(format!("Remote tag not found: {tag}"))
+                .map(|head| head.oid().to_string())
+        }
+        // A pinned revision doesn't track a moving remote ref, so the "latest" commit is just
+        // the pin itself, which `has_update` compares against the installed commit by prefix.
+        GitRef::Rev(rev) => Ok(rev.clone()),
+    }
+}
+
 fn get_registry_package_path(name: &str) -> String {
     assert!(
         !name.is_empty(),
@@ -282,6 +483,19 @@ fn get_registry_package_path(name: &str) -> String {
 #[derive(serde::Deserialize)]
 struct RegistryVersion {
     vers: Version,
+    #[serde(default)]
+    yanked: bool,
+}
+
+/// Whether an update from `current` to `latest` is semver-breaking, using the same rule cargo
+/// uses for caret requirements: a differing major version, or (for `0.x` releases) a differing
+/// minor version.
+fn is_breaking_update(current: &Version, latest: &Version) -> bool {
+    if current.major != 0 || latest.major != 0 {
+        current.major != latest.major
+    } else {
+        current.minor != latest.minor
+    }
 }
 
 struct LatestPackage {
@@ -294,6 +508,7 @@ enum LatestUpstream {
         url: String,
         commit: String,
         latest_commit: String,
+        git_ref: GitRef,
     },
     Registry {
         version: Version,
@@ -307,8 +522,17 @@ impl LatestPackage {
             LatestUpstream::Git {
                 commit,
                 latest_commit,
+                git_ref,
                 ..
-            } => commit != latest_commit,
+            } => {
+                // `?rev=` preserves whatever the user passed cargo, often a short SHA, while
+                // `commit` from `.crates.toml` is always the full 40-char hash. Compare by
+                // prefix so a rev-pinned package doesn't look perpetually outdated.
+                match git_ref {
+                    GitRef::Rev(_) => !commit.starts_with(latest_commit.as_str()),
+                    _ => commit != latest_commit,
+                }
+            }
             LatestUpstream::Registry {
                 version,
                 latest_version,
@@ -317,10 +541,35 @@ impl LatestPackage {
         }
     }
 
+    /// Whether this package's update (if any) is semver-breaking. Git packages are never
+    /// considered breaking since they carry no version.
+    fn is_breaking(&self) -> bool {
+        match &self.upstream {
+            LatestUpstream::Git { .. } => false,
+            LatestUpstream::Registry {
+                version,
+                latest_version,
+                ..
+            } => is_breaking_update(version, latest_version),
+        }
+    }
+
+    /// Whether this package should be kept when `--compatible`/`--incompatible` was passed.
+    /// Packages without an update always pass, since there is nothing to classify.
+    fn matches_compat_filter(&self, compatible: bool, incompatible: bool) -> bool {
+        if !self.has_update() {
+            return true;
+        }
+        let breaking = self.is_breaking();
+        (!compatible || !breaking) && (!incompatible || breaking)
+    }
+
     fn print(&self, len: usize) {
         let update = self.has_update();
         let name = &self.name;
-        let status = if update {
+        let status = if update && self.is_breaking() {
+            "Breaking".style(*STATUS_BREAKING_STYLE)
+        } else if update {
             "Update".style(*STATUS_UPDATE_STYLE)
         } else {
             "Current".style(*STATUS_CURRENT_STYLE)
@@ -354,15 +603,101 @@ impl LatestPackage {
         }
     }
 
-    fn update(&self, jobs: Option<u8>, locked: bool, verbose: bool) -> Result<()> {
-        if self.has_update() {
-            update(self, jobs, locked, verbose)?;
+    /// Build the machine-readable representation of this package for `--format json`.
+    fn report(&self) -> PackageReport {
+        let update = self.has_update();
+        match &self.upstream {
+            LatestUpstream::Git {
+                commit,
+                latest_commit,
+                ..
+            } => PackageReport {
+                name: self.name.clone(),
+                source: SourceKind::Git,
+                installed: commit.clone(),
+                available: latest_commit.clone(),
+                update,
+                update_class: None,
+            },
+            LatestUpstream::Registry {
+                version,
+                latest_version,
+                ..
+            } => PackageReport {
+                name: self.name.clone(),
+                source: SourceKind::Registry,
+                installed: version.to_string(),
+                available: latest_version.to_string(),
+                update,
+                update_class: update.then(|| classify_update(version, latest_version)),
+            },
         }
-        Ok(())
     }
 }
 
-fn update(pkg: &LatestPackage, jobs: Option<u8>, locked: bool, verbose: bool) -> Result<()> {
+/// The full `--format json` document: every package's update status plus, in manifest mode, the
+/// pending install/uninstall plan. Kept together so piping into `jq`/CI sees the same
+/// reconciliation plan the table output prints via `manifest::print_plan`.
+#[derive(serde::Serialize)]
+struct JsonReport {
+    packages: Vec<PackageReport>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    manifest_actions: Vec<manifest::ManifestActionReport>,
+}
+
+/// Machine-readable description of a single package's update status, used for `--format json`.
+#[derive(serde::Serialize)]
+struct PackageReport {
+    name: String,
+    source: SourceKind,
+    installed: String,
+    available: String,
+    update: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    update_class: Option<UpdateClass>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+enum SourceKind {
+    Git,
+    Registry,
+}
+
+/// How large a registry update is, by the highest-order version component that changed.
+#[derive(serde::Serialize, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+enum UpdateClass {
+    Patch,
+    Minor,
+    Major,
+}
+
+fn classify_update(current: &Version, latest: &Version) -> UpdateClass {
+    if current.major != latest.major {
+        UpdateClass::Major
+    } else if current.minor != latest.minor {
+        UpdateClass::Minor
+    } else {
+        UpdateClass::Patch
+    }
+}
+
+fn update(
+    pkg: &LatestPackage,
+    jobs: Option<u8>,
+    locked: bool,
+    verbose: bool,
+    binstall: bool,
+) -> Result<()> {
+    if binstall {
+        if let LatestUpstream::Registry { latest_version, .. } = &pkg.upstream {
+            if *BINSTALL_AVAILABLE && binstall_update(&pkg.name, latest_version, verbose)? {
+                return Ok(());
+            }
+        }
+    }
+
     let mut command = Command::new("cargo");
     command.arg("install");
 
@@ -377,8 +712,21 @@ fn update(pkg: &LatestPackage, jobs: Option<u8>, locked: bool, verbose: bool) ->
     }
 
     match &pkg.upstream {
-        LatestUpstream::Git { url, .. } => {
-            command.arg("--git").arg(url).arg(&pkg.name);
+        LatestUpstream::Git { url, git_ref, .. } => {
+            command.arg("--git").arg(url);
+            match git_ref {
+                GitRef::Branch(branch) => {
+                    command.arg("--branch").arg(branch);
+                }
+                GitRef::Tag(tag) => {
+                    command.arg("--tag").arg(tag);
+                }
+                GitRef::Rev(rev) => {
+                    command.arg("--rev").arg(rev);
+                }
+                GitRef::Default => {}
+            }
+            command.arg(&pkg.name);
         }
         LatestUpstream::Registry { .. } => {
             command.arg(&pkg.name);
@@ -389,12 +737,37 @@ fn update(pkg: &LatestPackage, jobs: Option<u8>, locked: bool, verbose: bool) ->
     Ok(())
 }
 
+/// Try installing `name@version` via `cargo binstall`, fetching exactly the version `cargo-syu`
+/// already resolved rather than letting binstall re-resolve it. Returns whether it succeeded;
+/// callers should fall back to `cargo install` when it didn't, e.g. no prebuilt artifact exists
+/// for this target triple.
+fn binstall_update(name: &str, version: &Version, verbose: bool) -> Result<bool> {
+    let mut command = Command::new("cargo");
+    command.arg("binstall").arg("--no-confirm");
+    if verbose {
+        command.arg("--verbose");
+    }
+    command.arg(format!("{name}@{version}"));
+    Ok(command.spawn()?.wait()?.success())
+}
+
+/// Whether `cargo-binstall` is available on `PATH`. Checked once and cached, since it requires a
+/// filesystem scan.
+static BINSTALL_AVAILABLE: LazyLock<bool> = LazyLock::new(|| {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join("cargo-binstall").exists()))
+        .unwrap_or(false)
+});
+
 static STATUS_CURRENT_STYLE: LazyLock<owo_colors::Style> =
     LazyLock::new(|| owo_colors::Style::new().bold().bright_black());
 
-static STATUS_UPDATE_STYLE: LazyLock<owo_colors::Style> =
+pub(crate) static STATUS_UPDATE_STYLE: LazyLock<owo_colors::Style> =
     LazyLock::new(|| owo_colors::Style::new().bold().green());
 
+static STATUS_BREAKING_STYLE: LazyLock<owo_colors::Style> =
+    LazyLock::new(|| owo_colors::Style::new().bold().yellow());
+
 static VERSION_CURRENT_STYLE: LazyLock<owo_colors::Style> =
     LazyLock::new(|| owo_colors::Style::new().bright_black());
 
@@ -413,6 +786,18 @@ mod tests {
         assert_eq!(get_registry_package_path("cargo-syu"), "ca/rg/cargo-syu");
     }
 
+    #[test]
+    fn git_ref_parses_query() {
+        assert_eq!(GitRef::from_query("branch=main"), GitRef::Branch("main".to_owned()));
+        assert_eq!(GitRef::from_query("tag=v1.0.0"), GitRef::Tag("v1.0.0".to_owned()));
+        assert_eq!(
+            GitRef::from_query("rev=ccd28e7939cf3feed230944cfc3a0498b98bddab"),
+            GitRef::Rev("ccd28e7939cf3feed230944cfc3a0498b98bddab".to_owned())
+        );
+        assert_eq!(GitRef::from_query(""), GitRef::Default);
+        assert_eq!(GitRef::from_query("unknown=value"), GitRef::Default);
+    }
+
     #[test]
     fn package_has_update() {
         assert!(LatestPackage {
@@ -436,7 +821,8 @@ mod tests {
             upstream: LatestUpstream::Git {
                 url: "".to_owned(),
                 commit: "ccd28e7939cf3feed230944cfc3a0498b98bddab".to_owned(),
-                latest_commit: "bb9f36d2fd022a089d39455d86d6c14e572628f1".to_owned()
+                latest_commit: "bb9f36d2fd022a089d39455d86d6c14e572628f1".to_owned(),
+                git_ref: GitRef::Default,
             },
         }
         .has_update());
@@ -445,9 +831,56 @@ mod tests {
             upstream: LatestUpstream::Git {
                 url: "".to_owned(),
                 commit: "ccd28e7939cf3feed230944cfc3a0498b98bddab".to_owned(),
-                latest_commit: "ccd28e7939cf3feed230944cfc3a0498b98bddab".to_owned()
+                latest_commit: "ccd28e7939cf3feed230944cfc3a0498b98bddab".to_owned(),
+                git_ref: GitRef::Default,
+            },
+        }
+        .has_update());
+    }
+
+    #[test]
+    fn rev_pinned_git_package_compares_by_prefix() {
+        assert!(!LatestPackage {
+            name: "".to_owned(),
+            upstream: LatestUpstream::Git {
+                url: "".to_owned(),
+                commit: "ccd28e7939cf3feed230944cfc3a0498b98bddab".to_owned(),
+                latest_commit: "ccd28e79".to_owned(),
+                git_ref: GitRef::Rev("ccd28e79".to_owned()),
+            },
+        }
+        .has_update());
+        assert!(LatestPackage {
+            name: "".to_owned(),
+            upstream: LatestUpstream::Git {
+                url: "".to_owned(),
+                commit: "ccd28e7939cf3feed230944cfc3a0498b98bddab".to_owned(),
+                latest_commit: "bb9f36d2".to_owned(),
+                git_ref: GitRef::Rev("bb9f36d2".to_owned()),
             },
         }
         .has_update());
     }
+
+    #[test]
+    fn breaking_update_classification() {
+        assert!(!is_breaking_update(&Version::new(1, 0, 0), &Version::new(1, 1, 0)));
+        assert!(is_breaking_update(&Version::new(1, 0, 0), &Version::new(2, 0, 0)));
+        // Pre-1.0 crates treat the minor version as the breaking component.
+        assert!(is_breaking_update(&Version::new(0, 1, 0), &Version::new(0, 2, 0)));
+        assert!(!is_breaking_update(&Version::new(0, 1, 0), &Version::new(0, 1, 1)));
+    }
+
+    #[test]
+    fn compat_filter_keeps_current_packages() {
+        let current = LatestPackage {
+            name: "".to_owned(),
+            upstream: LatestUpstream::Registry {
+                version: Version::new(1, 0, 0),
+                latest_version: Version::new(1, 0, 0),
+            },
+        };
+        assert!(current.matches_compat_filter(true, false));
+        assert!(current.matches_compat_filter(false, true));
+    }
 }
@@ -0,0 +1,229 @@
+//! Declarative package manifest support.
+//!
+//! A manifest describes the desired set of installed crates, similar to how an ansible-style
+//! installer declares a resource's desired state. `cargo syu --manifest <path>` reconciles the
+//! currently installed packages (from `.crates.toml`) against the manifest instead of
+//! unconditionally updating everything that is already installed.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::Result;
+use owo_colors::OwoColorize as _;
+use serde::Deserialize;
+
+use crate::{Package, STATUS_UPDATE_STYLE};
+
+/// The desired state of a single crate, as declared in a manifest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DesiredState {
+    /// Install the crate if missing, and keep it up to date (the default `cargo syu` behavior).
+    Latest,
+    /// Install the crate if missing, but otherwise leave its version alone.
+    Present,
+    /// Uninstall the crate.
+    Absent,
+}
+
+/// A single manifest entry, either a bare state (`"latest"`) or a table with an optional
+/// alternate install root (`{ state = "present", root = "..." }`).
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ManifestEntry {
+    State(DesiredState),
+    Table {
+        state: DesiredState,
+        root: Option<PathBuf>,
+    },
+}
+
+impl ManifestEntry {
+    pub(crate) fn state(&self) -> DesiredState {
+        match self {
+            Self::State(state) | Self::Table { state, .. } => *state,
+        }
+    }
+
+    fn root(&self) -> Option<&Path> {
+        match self {
+            Self::State(_) => None,
+            Self::Table { root, .. } => root.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct Manifest {
+    pub(crate) packages: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    pub(crate) fn read(path: &Path) -> Result<Self> {
+        let data = std::fs::read_to_string(path)?;
+        Ok(toml::from_str(&data)?)
+    }
+
+    /// The default manifest location, `~/.config/cargo-syu/packages.toml`.
+    pub(crate) fn default_path() -> Option<PathBuf> {
+        Some(home::home_dir()?.join(".config/cargo-syu/packages.toml"))
+    }
+}
+
+/// A reconciliation step produced by diffing a [`Manifest`] against the installed packages.
+pub(crate) enum ManifestAction {
+    /// Install a crate the manifest wants that isn't installed yet.
+    Install { name: String, root: Option<PathBuf> },
+    /// Uninstall a crate the manifest no longer wants.
+    Uninstall { name: String, root: Option<PathBuf> },
+}
+
+impl ManifestAction {
+    pub(crate) fn name(&self) -> &str {
+        match self {
+            Self::Install { name, .. } | Self::Uninstall { name, .. } => name,
+        }
+    }
+
+    /// Run the action, shelling out to `cargo install`/`cargo uninstall`.
+    pub(crate) fn run(&self) -> Result<()> {
+        let mut command = Command::new("cargo");
+        match self {
+            Self::Install { name, root } => {
+                command.arg("install").arg(name);
+                if let Some(root) = root {
+                    command.arg("--root").arg(root);
+                }
+            }
+            Self::Uninstall { name, root } => {
+                command.arg("uninstall").arg(name);
+                if let Some(root) = root {
+                    command.arg("--root").arg(root);
+                }
+            }
+        }
+        let status = command.spawn()?.wait()?;
+        if !status.success() {
+            anyhow::bail!("cargo exited with {status}");
+        }
+        Ok(())
+    }
+
+    /// Build the machine-readable representation of this action for `--format json`.
+    pub(crate) fn report(&self) -> ManifestActionReport {
+        match self {
+            Self::Install { name, root } => ManifestActionReport {
+                name: name.clone(),
+                action: ManifestActionKind::Install,
+                root: root.clone(),
+            },
+            Self::Uninstall { name, root } => ManifestActionReport {
+                name: name.clone(),
+                action: ManifestActionKind::Uninstall,
+                root: root.clone(),
+            },
+        }
+    }
+}
+
+/// Machine-readable description of a single manifest reconciliation step, used for
+/// `--format json`.
+#[derive(serde::Serialize)]
+pub(crate) struct ManifestActionReport {
+    pub(crate) name: String,
+    pub(crate) action: ManifestActionKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) root: Option<PathBuf>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum ManifestActionKind {
+    Install,
+    Uninstall,
+}
+
+/// Diff a manifest against the currently installed packages, producing the install/uninstall
+/// plan needed to reconcile the two. Crates with a `latest` state that are already installed are
+/// left out of the plan; they flow through the normal update pipeline instead.
+pub(crate) fn plan(manifest: &Manifest, installed: &[Package]) -> Vec<ManifestAction> {
+    manifest
+        .packages
+        .iter()
+        .filter_map(|(name, entry)| {
+            let is_installed = installed.iter().any(|pkg| &pkg.name == name);
+            match (entry.state(), is_installed) {
+                (DesiredState::Absent, true) => Some(ManifestAction::Uninstall {
+                    name: name.clone(),
+                    root: entry.root().map(Path::to_path_buf),
+                }),
+                (DesiredState::Latest | DesiredState::Present, false) => {
+                    Some(ManifestAction::Install {
+                        name: name.clone(),
+                        root: entry.root().map(Path::to_path_buf),
+                    })
+                }
+                (DesiredState::Absent, false) | (DesiredState::Present, true) => None,
+                (DesiredState::Latest, true) => None,
+            }
+        })
+        .collect()
+}
+
+/// Print the install/uninstall plan in the same aligned table style as the update report.
+pub(crate) fn print_plan(actions: &[ManifestAction]) {
+    if actions.is_empty() {
+        return;
+    }
+    let len = actions
+        .iter()
+        .map(|action| action.name().len())
+        .max()
+        .unwrap_or(7)
+        .max(7);
+    println!("{:>12} {:<len$}", "Plan".bold().green(), "Package");
+    for action in actions {
+        let verb = match action {
+            ManifestAction::Install { .. } => "Install".style(*STATUS_UPDATE_STYLE),
+            ManifestAction::Uninstall { .. } => "Remove".style(*STATUS_UPDATE_STYLE),
+        };
+        println!("{verb:>12} {:<len$}", action.name());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plan_reconciles_manifest_against_installed() {
+        let manifest = Manifest {
+            packages: BTreeMap::from([
+                ("present-and-missing".to_owned(), ManifestEntry::State(DesiredState::Present)),
+                ("present-and-installed".to_owned(), ManifestEntry::State(DesiredState::Present)),
+                ("latest-and-missing".to_owned(), ManifestEntry::State(DesiredState::Latest)),
+                ("latest-and-installed".to_owned(), ManifestEntry::State(DesiredState::Latest)),
+                ("absent-and-installed".to_owned(), ManifestEntry::State(DesiredState::Absent)),
+                ("absent-and-missing".to_owned(), ManifestEntry::State(DesiredState::Absent)),
+            ]),
+        };
+        let installed = ["present-and-installed", "latest-and-installed", "absent-and-installed"]
+            .into_iter()
+            .map(|name| Package {
+                name: name.to_owned(),
+                upstream: crate::Upstream::Registry {
+                    version: semver::Version::new(1, 0, 0),
+                },
+            })
+            .collect::<Vec<_>>();
+
+        let mut actions = plan(&manifest, &installed);
+        actions.sort_by(|a, b| a.name().cmp(b.name()));
+        let names = actions.iter().map(ManifestAction::name).collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            ["absent-and-installed", "latest-and-missing", "present-and-missing"]
+        );
+    }
+}